@@ -0,0 +1,109 @@
+//! Interactive actions registered with `register_command` in `CorePluginInit`, shown in
+//! Binary Ninja's "iBoot" command menu rather than running automatically on load.
+
+use binaryninja::binary_view::{BinaryView, BinaryViewExt};
+use binaryninja::interaction::get_integer_input;
+use binaryninja::section::{Section, Semantics};
+use binaryninja::segment::{Segment, SegmentFlags};
+use binaryninja::symbol::{Symbol, SymbolType};
+use log::{info, warn};
+
+use crate::symbols::read_version_string;
+
+/// "Show iBoot version" - surfaces the build string the loader recovered at load time.
+pub fn show_version(view: &BinaryView) {
+    match read_version_string(view) {
+        Some(version) => info!("iBoot version: {version}"),
+        None => warn!("Could not read an iBoot version string from this view"),
+    }
+}
+
+/// "Rebase image" - lets the analyst override the auto-detected base address when
+/// `find_base_addr` guessed wrong, rebuilding the segment, section, and entry point at the
+/// new base.
+pub fn rebase_image(view: &BinaryView) {
+    let current_base = view.start();
+    let len = view.len();
+
+    let new_base = match get_integer_input("New base address", "Rebase iBoot image") {
+        Some(value) => value as u64,
+        None => return,
+    };
+
+    if new_base == current_base {
+        info!("Base address unchanged ({:#x})", current_base);
+        return;
+    }
+
+    view.remove_auto_segment(current_base, len);
+    view.remove_auto_section("iBoot");
+
+    let segment_flags = SegmentFlags::new()
+        .readable(true)
+        .writable(false)
+        .executable(true)
+        .contains_data(true)
+        .contains_code(true);
+
+    view.add_segment(
+        Segment::builder(new_base..new_base + len)
+            .parent_backing(0..len)
+            .is_auto(true)
+            .flags(segment_flags),
+    );
+    view.add_section(
+        Section::builder("iBoot".to_string(), new_base..new_base + len)
+            .semantics(Semantics::ReadOnlyCode)
+            .is_auto(true),
+    );
+
+    view.add_entry_point(new_base);
+    let start = Symbol::builder(SymbolType::Function, "_start", new_base).create();
+    view.define_auto_symbol(&start);
+
+    info!("Rebased from {:#x} to {:#x}", current_base, new_base);
+}
+
+/// String literals known to sit right next to (or be referenced by) interesting iBoot
+/// functions, used by `label_functions_from_strings` below.
+const LABEL_STRINGS: &[&str] = &["AppleImage4", "_panic", "do_go"];
+
+/// "Label functions from strings" - cross-references recognizable string literals to the
+/// functions that reference them and renames those functions after the string. When more
+/// than one function references the same string (easily possible for something generic like
+/// `"_panic"`), the bare needle name would collide across all of them, so every one of those
+/// functions gets the needle name suffixed with its own address.
+pub fn label_functions_from_strings(view: &BinaryView) {
+    let mut labeled = 0usize;
+
+    for needle in LABEL_STRINGS {
+        let mut funcs = Vec::new();
+        for string_ref in view.strings() {
+            if !string_ref.value().contains(needle) {
+                continue;
+            }
+
+            for code_ref in view.code_refs(string_ref.start()) {
+                if let Some(func) = code_ref.function() {
+                    funcs.push(func.start());
+                }
+            }
+        }
+        funcs.sort_unstable();
+        funcs.dedup();
+
+        let collides = funcs.len() > 1;
+        for start in funcs {
+            let name = if collides {
+                format!("{needle}_{start:#x}")
+            } else {
+                needle.to_string()
+            };
+            let symbol = Symbol::builder(SymbolType::Function, name, start).create();
+            view.define_auto_symbol(&symbol);
+            labeled += 1;
+        }
+    }
+
+    info!("Labeled {labeled} function(s) from string references");
+}
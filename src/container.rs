@@ -0,0 +1,258 @@
+//! Unwrapping logic for the container formats Apple ships iBoot/SecureROM payloads in:
+//! an optional outer IM4P (ASN.1 DER) envelope, and an optional inner `complzss` compressed
+//! blob. `unwrap_payload` is the single entry point `iBootView::init` should call before any
+//! offset-based parsing (`find_base_addr`, `get_iboot_version`, ...) touches the buffer.
+
+use log::{info, warn};
+
+const LZSS_MAGIC: &[u8; 8] = b"complzss";
+const LZSS_HEADER_LEN: usize = 0x180;
+
+const LZSS_RING_SIZE: usize = 4096;
+const LZSS_THRESHOLD: usize = 2;
+const LZSS_MAX_MATCH: usize = 18;
+
+/// Unwraps an IM4P container and/or `complzss` compression around a raw iBoot/SecureROM
+/// payload, returning the decoded image. If neither layer is present, `data` is handed back
+/// unchanged.
+pub fn unwrap_payload(data: &[u8]) -> Vec<u8> {
+    let stage1 = match unwrap_im4p(data) {
+        Some(payload) => {
+            info!("Unwrapped IM4P container ({} bytes)", payload.len());
+            payload
+        }
+        None => data.to_vec(),
+    };
+
+    match decompress_lzss(&stage1) {
+        Some(decompressed) => {
+            info!(
+                "Decompressed complzss payload ({} -> {} bytes)",
+                stage1.len(),
+                decompressed.len()
+            );
+            decompressed
+        }
+        None => stage1,
+    }
+}
+
+/// A single DER tag-length-value, along with the offset immediately following it.
+struct Tlv<'a> {
+    tag: u8,
+    content: &'a [u8],
+    next: usize,
+}
+
+/// Reads one DER TLV starting at `pos`, supporting both short and long form lengths.
+fn read_tlv(data: &[u8], pos: usize) -> Option<Tlv<'_>> {
+    let tag = *data.get(pos)?;
+    let len_byte = *data.get(pos + 1)? as usize;
+
+    let (len, header_len) = if len_byte & 0x80 == 0 {
+        (len_byte, 2)
+    } else {
+        let num_octets = len_byte & 0x7f;
+        if num_octets == 0 || num_octets > 4 {
+            return None;
+        }
+        let mut len = 0usize;
+        for i in 0..num_octets {
+            len = (len << 8) | *data.get(pos + 2 + i)? as usize;
+        }
+        (len, 2 + num_octets)
+    };
+
+    let content_start = pos + header_len;
+    let content_end = content_start.checked_add(len)?;
+    let content = data.get(content_start..content_end)?;
+    Some(Tlv {
+        tag,
+        content,
+        next: content_end,
+    })
+}
+
+/// Parses the outer IM4P DER SEQUENCE (`IM4P` marker, 4-char payload type, description,
+/// then an OCTET STRING holding the raw payload) and returns the OCTET STRING's contents.
+/// Returns `None` when `data` does not start with a recognizable IM4P envelope.
+fn unwrap_im4p(data: &[u8]) -> Option<Vec<u8>> {
+    const SEQUENCE_TAG: u8 = 0x30;
+    const IA5_STRING_TAG: u8 = 0x16;
+    const OCTET_STRING_TAG: u8 = 0x04;
+
+    let outer = read_tlv(data, 0)?;
+    if outer.tag != SEQUENCE_TAG {
+        return None;
+    }
+
+    let marker = read_tlv(outer.content, 0)?;
+    if marker.tag != IA5_STRING_TAG || marker.content != b"IM4P" {
+        return None;
+    }
+
+    let payload_type = read_tlv(outer.content, marker.next)?;
+    if payload_type.tag != IA5_STRING_TAG {
+        return None;
+    }
+
+    let description = read_tlv(outer.content, payload_type.next)?;
+    if description.tag != IA5_STRING_TAG {
+        return None;
+    }
+
+    let payload = read_tlv(outer.content, description.next)?;
+    if payload.tag != OCTET_STRING_TAG {
+        return None;
+    }
+
+    Some(payload.content.to_vec())
+}
+
+/// Decompresses an Apple `complzss`-compressed blob, stopping once `decompressed_size`
+/// output bytes have been produced. Returns `None` when `data` doesn't start with the
+/// `complzss` magic.
+fn decompress_lzss(data: &[u8]) -> Option<Vec<u8>> {
+    if data.len() < LZSS_HEADER_LEN || &data[0..8] != LZSS_MAGIC {
+        return None;
+    }
+
+    let decompressed_size = u32::from_be_bytes(data[12..16].try_into().unwrap()) as usize;
+    let compressed_size = u32::from_be_bytes(data[16..20].try_into().unwrap()) as usize;
+
+    let compressed = data.get(LZSS_HEADER_LEN..LZSS_HEADER_LEN + compressed_size)?;
+    let mut out = Vec::with_capacity(decompressed_size);
+
+    let mut ring = [b' '; LZSS_RING_SIZE];
+    let mut ring_pos = LZSS_RING_SIZE - LZSS_MAX_MATCH;
+
+    let mut pos = 0usize;
+    let mut flags: u32 = 0;
+
+    while pos < compressed.len() && out.len() < decompressed_size {
+        flags >>= 1;
+        if flags & 0x100 == 0 {
+            flags = *compressed.get(pos)? as u32 | 0xff00;
+            pos += 1;
+        }
+
+        if flags & 1 != 0 {
+            let byte = *compressed.get(pos)?;
+            pos += 1;
+            out.push(byte);
+            ring[ring_pos] = byte;
+            ring_pos = (ring_pos + 1) % LZSS_RING_SIZE;
+        } else {
+            let lo = *compressed.get(pos)? as usize;
+            let hi = *compressed.get(pos + 1)? as usize;
+            pos += 2;
+
+            let offset = lo | ((hi & 0xf0) << 4);
+            let length = (hi & 0x0f) + LZSS_THRESHOLD + 1;
+
+            for i in 0..length {
+                if out.len() >= decompressed_size {
+                    break;
+                }
+                let byte = ring[(offset + i) % LZSS_RING_SIZE];
+                out.push(byte);
+                ring[ring_pos] = byte;
+                ring_pos = (ring_pos + 1) % LZSS_RING_SIZE;
+            }
+        }
+    }
+
+    if out.len() < decompressed_size {
+        warn!(
+            "complzss stream ended early: got {} of {} expected bytes",
+            out.len(),
+            decompressed_size
+        );
+    }
+
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn der_tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut out = vec![tag];
+        if content.len() < 0x80 {
+            out.push(content.len() as u8);
+        } else {
+            let len_bytes = content.len().to_be_bytes();
+            let len_bytes = len_bytes.iter().skip_while(|&&b| b == 0).collect::<Vec<_>>();
+            out.push(0x80 | len_bytes.len() as u8);
+            out.extend(len_bytes);
+        }
+        out.extend_from_slice(content);
+        out
+    }
+
+    fn sample_im4p(payload: &[u8]) -> Vec<u8> {
+        let mut sequence_content = Vec::new();
+        sequence_content.extend(der_tlv(0x16, b"IM4P"));
+        sequence_content.extend(der_tlv(0x16, b"ibot"));
+        sequence_content.extend(der_tlv(0x16, b""));
+        sequence_content.extend(der_tlv(0x04, payload));
+        der_tlv(0x30, &sequence_content)
+    }
+
+    #[test]
+    fn unwrap_im4p_extracts_octet_string_payload() {
+        let payload = b"iBoot payload bytes";
+        let im4p = sample_im4p(payload);
+        assert_eq!(unwrap_im4p(&im4p), Some(payload.to_vec()));
+    }
+
+    #[test]
+    fn unwrap_im4p_rejects_non_im4p_input() {
+        assert_eq!(unwrap_im4p(b"iBoot..........."), None);
+    }
+
+    fn sample_lzss(decompressed: &[u8]) -> Vec<u8> {
+        // An all-literal-token stream: one flag byte (all bits set) per up-to-8 bytes.
+        let mut compressed = Vec::new();
+        for chunk in decompressed.chunks(8) {
+            compressed.push(0xff);
+            compressed.extend_from_slice(chunk);
+        }
+
+        let mut data = Vec::new();
+        data.extend_from_slice(LZSS_MAGIC);
+        data.extend_from_slice(&0u32.to_be_bytes()); // checksum, unused by decompress_lzss
+        data.extend_from_slice(&(decompressed.len() as u32).to_be_bytes());
+        data.extend_from_slice(&(compressed.len() as u32).to_be_bytes());
+        data.resize(LZSS_HEADER_LEN, 0);
+        data.extend_from_slice(&compressed);
+        data
+    }
+
+    #[test]
+    fn decompress_lzss_round_trips_literal_only_stream() {
+        let decompressed = b"Hello, iBoot!";
+        let data = sample_lzss(decompressed);
+        assert_eq!(decompress_lzss(&data), Some(decompressed.to_vec()));
+    }
+
+    #[test]
+    fn decompress_lzss_rejects_missing_magic() {
+        assert_eq!(decompress_lzss(b"not complzss data at all, padded out"), None);
+    }
+
+    #[test]
+    fn unwrap_payload_handles_im4p_wrapping_complzss() {
+        let decompressed = b"plain iBoot image bytes";
+        let lzss = sample_lzss(decompressed);
+        let im4p = sample_im4p(&lzss);
+        assert_eq!(unwrap_payload(&im4p), decompressed.to_vec());
+    }
+
+    #[test]
+    fn unwrap_payload_passes_through_unwrapped_data() {
+        let plain = b"already a plain iBoot image";
+        assert_eq!(unwrap_payload(plain), plain.to_vec());
+    }
+}
@@ -1,6 +1,14 @@
+use binaryninja::binary_view::register_binary_view_event;
+use binaryninja::binary_view::BinaryViewEventType;
+use binaryninja::command::register_command;
 use binaryninja::custom_binary_view::register_view_type;
+use binaryninja::debuginfo::register_debug_info_parser;
 use log::{info};
 
+mod commands;
+mod container;
+mod reloc;
+mod symbols;
 mod view;
 
 #[no_mangle]
@@ -9,6 +17,26 @@ pub extern "C" fn CorePluginInit() -> bool {
     info!("The logger has been initialized!");
 
     register_view_type("iBoot", "iBoot", view::iBootViewType::new);
+    register_debug_info_parser("iBoot", symbols::IBootDebugInfoParser);
+    register_binary_view_event(BinaryViewEventType::BinaryViewFinalizedEvent, |view| {
+        symbols::apply_on_load(view);
+    });
+
+    register_command(
+        "iBoot\\Show iBoot version",
+        "Show the iBoot build version string recovered from this view",
+        commands::show_version,
+    );
+    register_command(
+        "iBoot\\Rebase image",
+        "Override the auto-detected base address and rebuild segments around it",
+        commands::rebase_image,
+    );
+    register_command(
+        "iBoot\\Label functions from strings",
+        "Rename functions referencing recognizable iBoot string literals",
+        commands::label_functions_from_strings,
+    );
 
     true
 }
@@ -0,0 +1,254 @@
+//! Disassembly-driven corroboration of the image's link/runtime base address.
+//!
+//! `find_base_addr` reads the base address from a magic header offset that shifts (or
+//! disappears) across layout changes, with no way to cross-check it from file layout alone —
+//! nothing about a raw image says where its loader intends to place it in memory, so an
+//! absolute base can't be derived from the file by itself. What this checks instead is that the
+//! image holds genuine self-relocating startup code: it follows the reset vector to the real
+//! entry point, looks for the `ADRP`/`ADD` (or `ADR`) idiom that code uses to recompute its own
+//! runtime base, and requires several independent sites to agree on the same relative page.
+//! The header value itself is not examined; it is accepted whenever that check passes.
+
+use std::collections::HashMap;
+
+use log::warn;
+
+const SCAN_WINDOW: usize = 64;
+/// Minimum number of independent sites that must agree on the same relative page before the
+/// header-read base address is considered corroborated by the disassembly.
+const MIN_VOTES: usize = 3;
+
+fn sign_extend(value: u64, bits: u32) -> i64 {
+    let shift = 64 - bits;
+    ((value << shift) as i64) >> shift
+}
+
+fn read_insn(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+/// Decodes an unconditional `B <imm26>` at `offset`, returning the absolute byte offset of
+/// its target within the image.
+fn decode_branch(data: &[u8], offset: usize) -> Option<usize> {
+    let insn = read_insn(data, offset)?;
+    if insn >> 26 != 0b000101 {
+        return None;
+    }
+    let imm26 = (insn & 0x3ff_ffff) as u64;
+    let delta = sign_extend(imm26, 26) << 2;
+    offset.checked_add_signed(delta as isize)
+}
+
+/// Decodes `ADRP`/`ADR Xd, #imm` at `offset`. Returns `(is_page_form, rd, imm)`, where `imm`
+/// is the sign-extended raw immediate, a page count for the `ADRP` form (the processor's own
+/// `<< 12` is applied by the caller, not here).
+fn decode_adr(data: &[u8], offset: usize) -> Option<(bool, u8, i64)> {
+    let insn = read_insn(data, offset)?;
+    if (insn >> 24) & 0x1f != 0b10000 {
+        return None;
+    }
+    let is_page = (insn >> 31) & 1 == 1;
+    let immlo = (insn >> 29) & 0x3;
+    let immhi = ((insn >> 5) & 0x7_ffff) as u64;
+    let imm = sign_extend((immhi << 2) | immlo as u64, 21);
+    let rd = (insn & 0x1f) as u8;
+    Some((is_page, rd, imm))
+}
+
+/// Decodes a 64-bit `ADD Xd, Xn, #imm12{, LSL #12}` at `offset`. Returns `(rd, rn, imm)`.
+fn decode_add_imm(data: &[u8], offset: usize) -> Option<(u8, u8, u64)> {
+    let insn = read_insn(data, offset)?;
+    let sf = (insn >> 31) & 1;
+    let op_s = (insn >> 29) & 0x3;
+    let group = (insn >> 23) & 0x3f;
+    if sf != 1 || op_s != 0 || group != 0b100010 {
+        return None;
+    }
+    let shift12 = (insn >> 22) & 1 == 1;
+    let imm12 = ((insn >> 10) & 0xfff) as u64;
+    let rn = ((insn >> 5) & 0x1f) as u8;
+    let rd = (insn & 0x1f) as u8;
+    Some((rd, rn, if shift12 { imm12 << 12 } else { imm12 }))
+}
+
+/// A decoded self-relocation candidate: the page (for `ADRP`) or exact address (for `ADR`)
+/// it resolves to, assuming a link base of 0.
+fn scan_sites(data: &[u8], start: usize) -> Vec<i64> {
+    let mut sites = Vec::new();
+
+    for offset in (start..start.saturating_add(SCAN_WINDOW * 4)).step_by(4) {
+        let (is_page, rd, imm) = match decode_adr(data, offset) {
+            Some(decoded) => decoded,
+            None => continue,
+        };
+
+        if !is_page {
+            sites.push(offset as i64 + imm);
+            continue;
+        }
+
+        // Only count `ADRP` sites that are actually followed by the matching `ADD` that
+        // completes the idiom; a bare `ADRP` with no use of its result is more likely a
+        // misdecoded byte sequence than genuine self-relocation code.
+        let paired = (offset + 4..offset + 4 * 4)
+            .step_by(4)
+            .filter_map(|add_offset| decode_add_imm(data, add_offset))
+            .any(|(add_rd, add_rn, _)| add_rd == rd && add_rn == rd);
+        if !paired {
+            continue;
+        }
+
+        let page = (offset as i64 & !0xfff) + (imm << 12);
+        sites.push(page);
+    }
+
+    sites
+}
+
+/// Follows the reset vector's branch to `_start`, then scans for `ADRP`/`ADD` (or `ADR`)
+/// self-relocation sites. Each site's decoded value is the relative page (or address) it
+/// resolves to, assuming a link base of 0; whether that's the *true* base is not something
+/// file layout alone can answer, so this only asks whether multiple independent sites agree
+/// with each other. Real globals tend to be referenced from many call sites, so several of
+/// them resolving to the same relative page is genuine corroboration that real self-
+/// relocation code was found, whereas a single misdecoded instruction has nothing to agree
+/// with. Returns `true` once some page collects at least `MIN_VOTES` independent sites.
+fn disassembly_corroborates_self_relocation(data: &[u8]) -> bool {
+    let start = decode_branch(data, 0).unwrap_or(0);
+    let sites = scan_sites(data, start);
+    if sites.is_empty() {
+        return false;
+    }
+
+    let mut votes: HashMap<i64, usize> = HashMap::new();
+    for &site in &sites {
+        *votes.entry(site).or_insert(0) += 1;
+    }
+
+    votes.values().any(|&count| count >= MIN_VOTES)
+}
+
+/// Validates `header` (the legacy magic-offset base-address read) against the disassembly.
+/// Nothing in a raw image says where its own loader intends to place it in memory, so this
+/// can't derive a base address independently of `header`. Returns `Some(header)` when
+/// [`disassembly_corroborates_self_relocation`] finds self-relocating startup code in `data`,
+/// `None` otherwise. `header` itself is passed through unexamined, so a stale or garbage value
+/// is returned as-is whenever that check passes.
+pub fn recover_base_addr(data: &[u8], header: u64) -> Option<u64> {
+    if disassembly_corroborates_self_relocation(data) {
+        Some(header)
+    } else {
+        None
+    }
+}
+
+/// Reconciles the disassembly-corroborated base against the header-offset read, warning when
+/// the disassembly couldn't corroborate it, and falling back to the header value either way.
+pub fn reconcile(corroborated: Option<u64>, header: u64) -> u64 {
+    match corroborated {
+        Some(base) => base,
+        None => {
+            warn!(
+                "Could not corroborate base address {:#x} from disassembly, using the header offset read as-is",
+                header
+            );
+            header
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_branch(offset: usize, target: usize) -> u32 {
+        let delta = (target as i64 - offset as i64) >> 2;
+        (0b000101 << 26) | (delta as u32 & 0x3ff_ffff)
+    }
+
+    fn encode_adrp(rd: u8, imm_pages: i64) -> u32 {
+        let immlo = (imm_pages & 0x3) as u32;
+        let immhi = ((imm_pages >> 2) & 0x7_ffff) as u32;
+        (1 << 31) | (immlo << 29) | (0b10000 << 24) | (immhi << 5) | rd as u32
+    }
+
+    fn encode_add_imm(rd: u8, rn: u8, imm12: u64) -> u32 {
+        (1 << 31) | (0b100010 << 23) | ((imm12 as u32 & 0xfff) << 10) | ((rn as u32) << 5) | rd as u32
+    }
+
+    fn write_insn(data: &mut [u8], offset: usize, insn: u32) {
+        data[offset..offset + 4].copy_from_slice(&insn.to_le_bytes());
+    }
+
+    #[test]
+    fn decode_branch_follows_forward_target() {
+        let mut data = vec![0u8; 0x40];
+        write_insn(&mut data, 0, encode_branch(0, 0x20));
+        assert_eq!(decode_branch(&data, 0), Some(0x20));
+    }
+
+    #[test]
+    fn decode_adr_roundtrips_adrp_immediate() {
+        let mut data = vec![0u8; 0x10];
+        write_insn(&mut data, 0, encode_adrp(0, 5));
+        assert_eq!(decode_adr(&data, 0), Some((true, 0, 5)));
+    }
+
+    #[test]
+    fn decode_add_imm_roundtrips() {
+        let mut data = vec![0u8; 0x10];
+        write_insn(&mut data, 0, encode_add_imm(0, 0, 0x123));
+        assert_eq!(decode_add_imm(&data, 0), Some((0, 0, 0x123)));
+    }
+
+    /// Several call sites independently computing the address of the same globals page (a
+    /// common idiom) should corroborate an arbitrary header-read candidate: `recover_base_addr`
+    /// doesn't derive a base address of its own from the sites, so the expected result is the
+    /// `header` value passed in, not anything computed from the scenario's layout.
+    #[test]
+    fn recover_base_addr_returns_header_when_corroborated() {
+        let image_len = 0x4000usize;
+        let mut data = vec![0u8; image_len];
+
+        let header: u64 = 0x1800_0000_0000;
+        let globals_page_offset: i64 = 0x3000;
+
+        write_insn(&mut data, 0, encode_branch(0, 0x40));
+
+        let sites = [(0x40usize, 0x10u64), (0x80usize, 0x40u64), (0xc0usize, 0x120u64)];
+        for &(site_offset, imm12) in &sites {
+            write_insn(&mut data, site_offset, encode_adrp(0, globals_page_offset >> 12));
+            write_insn(&mut data, site_offset + 4, encode_add_imm(0, 0, imm12));
+        }
+
+        assert_eq!(recover_base_addr(&data, header), Some(header));
+    }
+
+    #[test]
+    fn recover_base_addr_rejects_uncorroborated_single_site() {
+        let image_len = 0x4000usize;
+        let mut data = vec![0u8; image_len];
+        write_insn(&mut data, 0, encode_branch(0, 0x40));
+        write_insn(&mut data, 0x40, encode_adrp(0, 3));
+        write_insn(&mut data, 0x44, encode_add_imm(0, 0, 0x321));
+
+        assert_eq!(recover_base_addr(&data, 0x1000), None);
+    }
+
+    #[test]
+    fn recover_base_addr_returns_none_without_any_sites() {
+        let data = vec![0u8; 0x100];
+        assert_eq!(recover_base_addr(&data, 0x1000), None);
+    }
+
+    #[test]
+    fn reconcile_prefers_corroborated_result() {
+        assert_eq!(reconcile(Some(0x2000), 0x1000), 0x2000);
+    }
+
+    #[test]
+    fn reconcile_falls_back_to_header_when_uncorroborated() {
+        assert_eq!(reconcile(None, 0x1000), 0x1000);
+    }
+}
@@ -0,0 +1,192 @@
+//! Version-keyed symbol import: loads a JSON database mapping iBoot build strings (or
+//! prefixes thereof) to symbol-name -> image-relative-offset tables, relocates the offsets
+//! against the recovered `base_addr`, and registers each one as a `DebugInfo` function entry
+//! carrying its name and address. The database holds no types, and no data variables are
+//! defined.
+
+use std::collections::HashMap;
+
+use binaryninja::binary_view::{BinaryView, BinaryViewExt};
+use binaryninja::debuginfo::{CustomDebugInfoParser, DebugFunctionInfo, DebugInfo};
+use log::{info, warn};
+
+/// Bundled database, keyed by iBoot build-string substring (e.g. `"iBoot-7459.101.3"`).
+/// `IBOOT_SYMBOLS_JSON` lets analysts point at their own database instead.
+const BUNDLED_SYMBOL_DB: &str = include_str!("../data/symbols.json");
+
+type VersionTable = HashMap<String, u64>;
+
+struct SymbolDb {
+    versions: HashMap<String, VersionTable>,
+}
+
+impl SymbolDb {
+    fn load() -> Self {
+        let json = match std::env::var("IBOOT_SYMBOLS_JSON") {
+            Ok(path) => std::fs::read_to_string(&path).unwrap_or_else(|e| {
+                warn!("Failed to read {path}, falling back to bundled database: {e}");
+                BUNDLED_SYMBOL_DB.to_string()
+            }),
+            Err(_) => BUNDLED_SYMBOL_DB.to_string(),
+        };
+
+        let versions: HashMap<String, VersionTable> =
+            serde_json::from_str(&json).unwrap_or_else(|e| {
+                warn!("Failed to parse iBoot symbol database: {e}");
+                HashMap::new()
+            });
+
+        SymbolDb { versions }
+    }
+
+    /// Finds the table for `iboot_version`, preferring the longest key that's an exact
+    /// substring match (e.g. a specific `"iBoot-2696.0.0.1.0"` entry over a generic
+    /// `"iBoot-2696"` one also present in the database) and falling back to the longest key
+    /// sharing the same major-version prefix (e.g. matching key `"iBoot-7459.101.3"` for a
+    /// point release not itself in the database). Both branches break ties on key length so
+    /// the result doesn't depend on `HashMap` iteration order.
+    fn table_for(&self, iboot_version: &str) -> Option<&VersionTable> {
+        if let Some((_, table)) = self
+            .versions
+            .iter()
+            .filter(|(key, _)| iboot_version.contains(key.as_str()))
+            .max_by_key(|(key, _)| key.len())
+        {
+            return Some(table);
+        }
+
+        let prefix = iboot_version.split('.').next()?;
+        self.versions
+            .iter()
+            .filter(|(key, _)| key.starts_with(prefix))
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, table)| table)
+    }
+}
+
+/// Reads the build string `iBootView::get_iboot_version` recovers, trimmed of the
+/// trailing NUL padding, directly off a generic `BinaryView` handle. `0x286` is a file
+/// offset into the decoded image, not a virtual address, so it has to be read relative to
+/// the view's mapped base address (`view.start()`) rather than as a bare address.
+pub(crate) fn read_version_string(view: &BinaryView) -> Option<String> {
+    let raw = view.read_vec(view.start() + 0x286, 0x7a);
+    std::str::from_utf8(&raw)
+        .ok()
+        .map(|s| s.trim_end_matches('\0').to_string())
+}
+
+/// Looks up and registers symbols for `view`, relocating the database's image-relative
+/// offsets against `view`'s recovered base address.
+fn import_symbols(debug_info: &mut DebugInfo, view: &BinaryView) -> bool {
+    let iboot_version = match read_version_string(view) {
+        // The string at 0x286 starts at the version number (e.g. `7459.101.3`), while the
+        // database keys carry the `iBoot-` prefix.
+        Some(version) if !version.is_empty() => format!("iBoot-{version}"),
+        _ => {
+            warn!("Could not read iBoot version string, skipping symbol import");
+            return false;
+        }
+    };
+
+    let db = SymbolDb::load();
+    let table = match db.table_for(&iboot_version) {
+        Some(table) => table,
+        None => {
+            warn!("No symbol database entry for iBoot version {iboot_version}");
+            return false;
+        }
+    };
+
+    let base_addr = view.start();
+    for (name, offset) in table {
+        debug_info.add_function(DebugFunctionInfo::new(name.clone(), base_addr + offset));
+    }
+
+    info!("Imported {} symbols for {iboot_version}", table.len());
+    true
+}
+
+/// A `DebugInfoParser` that applies the version-keyed symbol database to any loaded
+/// `iBoot` view, registered alongside `iBootViewType` in `CorePluginInit`.
+pub struct IBootDebugInfoParser;
+
+impl CustomDebugInfoParser for IBootDebugInfoParser {
+    fn is_valid(&self, view: &BinaryView) -> bool {
+        view.type_name() == "iBoot"
+    }
+
+    fn parse(&self, debug_info: &mut DebugInfo, view: &BinaryView, _debug_file: &BinaryView) -> bool {
+        import_symbols(debug_info, view)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db(entries: &[(&str, &[(&str, u64)])]) -> SymbolDb {
+        let versions = entries
+            .iter()
+            .map(|&(version, symbols)| {
+                let table = symbols.iter().map(|&(name, off)| (name.to_string(), off)).collect();
+                (version.to_string(), table)
+            })
+            .collect();
+        SymbolDb { versions }
+    }
+
+    #[test]
+    fn table_for_prefers_exact_substring_match() {
+        let db = db(&[
+            ("iBoot-7459.101.2", &[("_main", 1)]),
+            ("iBoot-7459.101.3", &[("_main", 2)]),
+        ]);
+        let table = db.table_for("iBoot-7459.101.3.release").unwrap();
+        assert_eq!(table["_main"], 2);
+    }
+
+    #[test]
+    fn table_for_prefers_longest_exact_substring_match() {
+        // Both keys are substrings of the queried version (a generic entry alongside a more
+        // specific point release), so this must not depend on HashMap iteration order.
+        let db = db(&[
+            ("iBoot-2696", &[("_main", 1)]),
+            ("iBoot-2696.0.0.1.0", &[("_main", 2)]),
+        ]);
+        let table = db.table_for("iBoot-2696.0.0.1.0.release").unwrap();
+        assert_eq!(table["_main"], 2);
+    }
+
+    #[test]
+    fn table_for_falls_back_to_longest_matching_prefix() {
+        // Neither key is an exact substring of the queried version, so this only resolves
+        // via the major-version-prefix fallback, which should prefer the longer (more
+        // specific) of the two matching keys.
+        let db = db(&[
+            ("iBoot-7459.50.1", &[("_main", 1)]),
+            ("iBoot-7459.101.3", &[("_main", 2)]),
+        ]);
+        let table = db.table_for("iBoot-7459.77.2").unwrap();
+        assert_eq!(table["_main"], 2);
+    }
+
+    #[test]
+    fn table_for_returns_none_for_unknown_version() {
+        let db = db(&[("iBoot-7459.101.3", &[("_main", 1)])]);
+        assert!(db.table_for("iBoot-2817.1.1").is_none());
+    }
+}
+
+/// `register_binary_view_event` callback: runs the same import as `IBootDebugInfoParser`
+/// as soon as an `iBoot` view finishes loading, so symbols show up without the analyst
+/// having to trigger a manual "Update Analysis" debug-info pass.
+pub fn apply_on_load(view: &BinaryView) {
+    if view.type_name() != "iBoot" {
+        return;
+    }
+
+    let mut debug_info = DebugInfo::new();
+    if import_symbols(&mut debug_info, view) {
+        view.apply_debug_info(&debug_info);
+    }
+}
@@ -13,9 +13,11 @@ use binaryninja::custom_binary_view::{
     BinaryViewType, BinaryViewTypeBase, CustomBinaryView, CustomBinaryViewType, CustomView,
     CustomViewBuilder,
 };
-use binaryninja::data_buffer::DataBuffer;
 use binaryninja::Endianness;
 
+use crate::container;
+use crate::reloc;
+
 type BinaryViewResult<R> = binaryninja::binary_view::Result<R>;
 
 
@@ -55,7 +57,14 @@ impl BinaryViewTypeBase for iBootViewType {
                 return true;
             }
         }
-        false
+
+        // Wrapped payloads don't carry the plaintext tag at 0x200 until they've been
+        // unwrapped in `iBootView::init`, so also recognize the container formats directly.
+        if data.read_vec(0, b"complzss".len()) == b"complzss" {
+            return true;
+        }
+        let header = data.read_vec(0, 0x20);
+        header.windows(4).any(|w| w == b"IM4P")
     }
 }
 
@@ -78,6 +87,18 @@ impl CustomBinaryViewType for iBootViewType {
 pub struct iBootView {
     /// The handle to the "real" BinaryView object, in the Binary Ninja core.
     inner: binaryninja::rc::Ref<BinaryView>,
+    /// Pointer width in bytes (4 for armv7 SecureROM/iBoot/AVPBooter, 8 for aarch64),
+    /// detected in `init` before the first `address_size` query.
+    address_size: std::cell::Cell<usize>,
+    /// Endianness reported by `default_endianness`, set alongside `address_size` in `init`.
+    /// Both armv7 and aarch64 iBoot/SecureROM images run little-endian, so `init` sets
+    /// little-endian for either detected width.
+    endianness: std::cell::Cell<Endianness>,
+    /// The unwrapped/decompressed image `init` builds from the parent view's raw bytes via
+    /// `container::unwrap_payload`. `read` serves these bytes for the mapped segment, so the
+    /// hex view and disassembler see the decoded image rather than the original IM4P
+    /// envelope or `complzss`-compressed payload.
+    decoded: std::cell::RefCell<Vec<u8>>,
 }
 
 use std::str::Utf8Error;
@@ -85,24 +106,81 @@ impl iBootView {
     fn new(view: &BinaryView) -> Self {
         iBootView {
             inner: view.to_owned(),
+            address_size: std::cell::Cell::new(8),
+            endianness: std::cell::Cell::new(Endianness::LittleEndian),
+            decoded: std::cell::RefCell::new(Vec::new()),
+        }
+    }
+
+    /// The base-address field moved from `0x318` to `0x300` starting with `iBoot-6603.*`;
+    /// shared by [`detect_pointer_width`](Self::detect_pointer_width) and
+    /// [`header_base_addr`](Self::header_base_addr) so they always agree on where to look.
+    fn base_addr_field_offset(iboot_vers: &str) -> usize {
+        let major = iboot_vers
+            .split('.')
+            .next()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(0);
+        if major >= 6603 {
+            0x300
+        } else {
+            0x318
         }
     }
 
-    fn get_iboot_version(&self) -> Result<String, Utf8Error> {
-        let mut value = Vec::<u8>::new();
-        self.parent_view()
-            .expect("lol")
-            .read_into_vec(&mut value, 0x286, 0x7a);
+    /// Picks 32-bit armv7 vs. 64-bit aarch64 pointer width. The build version's major number
+    /// only narrows this down (a major below 2000 is taken as a hint for a 32-bit image):
+    /// 32-bit devices like the iPhone 5 and iPad 4 kept shipping version bumps
+    /// for years afterward, sharing version lineage with 64-bit devices, so a bare major
+    /// cutoff alone misclassifies later 32-bit images as aarch64. This corroborates that hint
+    /// against the header base-address field read as an 8-byte little-endian value: a genuine
+    /// 64-bit runtime pointer almost always has some bit set above bit 31, which a narrower
+    /// 4-byte pointer can never have, making that a reliable tell regardless of what the
+    /// version number alone suggests.
+    fn detect_pointer_width(iboot_vers: &str, decoded: &[u8]) -> usize {
+        let major = iboot_vers
+            .split('.')
+            .next()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(0);
+        let version_suggests_32bit = major != 0 && major < 2000;
+
+        let offset = Self::base_addr_field_offset(iboot_vers);
+        let wide_read = decoded
+            .get(offset..offset + 8)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u64::from_le_bytes);
+
+        if let Some(value) = wide_read {
+            if value > u64::from(u32::MAX) {
+                return 8;
+            }
+        }
+
+        if version_suggests_32bit {
+            4
+        } else {
+            8
+        }
+    }
+
+    fn get_iboot_version(&self, decoded: &[u8]) -> Result<String, Utf8Error> {
+        let value = decoded
+            .get(0x286..0x286 + 0x7a)
+            .unwrap_or_default()
+            .to_vec();
         match std::str::from_utf8(&value) {
-            Ok(iboot_version) => Ok(iboot_version.to_string()),
+            Ok(iboot_version) => Ok(iboot_version.trim_end_matches('\0').to_string()),
             Err(e) => Err(e),
         }
     }
 
-    fn find_base_addr(&self, buf: DataBuffer) -> u64 {
-        let mut base_addr_offset: usize = 0x318;
-
-        let iboot_vers: String = match self.get_iboot_version() {
+    /// Reads the base address from the legacy magic header offset. This is the value
+    /// [`find_base_addr`](Self::find_base_addr) returns; for aarch64 images the disassembly
+    /// check in `reloc` only decides whether a warning is logged. `width` selects a 4- or
+    /// 8-byte little-endian read for armv7 vs. aarch64 images.
+    fn header_base_addr(&self, decoded: &[u8], width: usize) -> u64 {
+        let iboot_vers: String = match self.get_iboot_version(decoded) {
             Ok(iboot_version_str) => iboot_version_str,
             Err(e) => {
                 error!("Error getting iBoot version : {e}");
@@ -110,31 +188,56 @@ impl iBootView {
             }
         };
 
-        let v: Vec<&str> = iboot_vers.split('.').collect();
-        if v[0].parse::<u64>().unwrap() >= 6603 {
-            base_addr_offset = 0x300
+        let base_addr_offset = Self::base_addr_field_offset(&iboot_vers);
+        let mut base_addr_buf = &decoded[base_addr_offset..base_addr_offset + width];
+        if width == 4 {
+            base_addr_buf
+                .read_u32::<LittleEndian>()
+                .unwrap_or_else(|e| {
+                    error!("Error {e}");
+                    0
+                }) as u64
+        } else {
+            base_addr_buf
+                .read_u64::<LittleEndian>()
+                .unwrap_or_else(|e| {
+                    error!("Error {e}");
+                    0
+                })
         }
+    }
 
-        let mut base_addr_buf = &buf.get_data()[base_addr_offset..base_addr_offset + 8];
-        base_addr_buf
-            .read_u64::<LittleEndian>()
-            .unwrap_or_else(|e| {
-                error!("Error {e}");
-                0
-            })
+    fn find_base_addr(&self, decoded: &[u8], width: usize) -> u64 {
+        let header = self.header_base_addr(decoded, width);
+        if width == 4 {
+            // The disassembly-driven recovery in `reloc` only understands aarch64.
+            return header;
+        }
+        reloc::reconcile(reloc::recover_base_addr(decoded, header), header)
     }
 
     fn init(&self) -> BinaryViewResult<()> {
         let parent_view = self.parent_view().ok_or(())?;
-        let parent_len = parent_view.len();
         let read_buffer = parent_view.read_buffer(0, parent_view.len() as usize)?;
-        let arch = CoreArchitecture::by_name("aarch64").ok_or(())?;
+        let decoded = container::unwrap_payload(read_buffer.get_data());
+        let decoded_len = decoded.len() as u64;
+
+        let iboot_vers = self.get_iboot_version(&decoded).unwrap_or_default();
+        let width = Self::detect_pointer_width(&iboot_vers, &decoded);
+        self.address_size.set(width);
+        // Both the armv7 (width 4) and aarch64 (width 8) targets iBoot ships for run
+        // little-endian, so the same value is set whichever width was detected.
+        self.endianness.set(Endianness::LittleEndian);
+        *self.decoded.borrow_mut() = decoded;
+
+        let arch_name = if width == 4 { "armv7" } else { "aarch64" };
+        let arch = CoreArchitecture::by_name(arch_name).ok_or(())?;
         let plat = arch.standalone_platform().ok_or(())?;
 
         self.set_default_arch(&arch);
         self.set_default_platform(&plat);
 
-        let base_addr = self.find_base_addr(read_buffer);
+        let base_addr = self.find_base_addr(&self.decoded.borrow(), width);
         info!("Base address at {:#09x}", base_addr);
 
         let segment_flags = SegmentFlags::new()
@@ -144,14 +247,16 @@ impl iBootView {
             .contains_data(true)
             .contains_code(true);
 
+        // `parent_backing` points into this view's own decoded bytes (served by `read`
+        // below), not the parent view's still-wrapped/compressed raw file.
         self.add_segment(
-            Segment::builder(base_addr..base_addr + parent_len)
-                .parent_backing(parent_view.start()..parent_view.len())
+            Segment::builder(base_addr..base_addr + decoded_len)
+                .parent_backing(0..decoded_len)
                 .is_auto(true).flags(segment_flags)
         );
 
         self.add_section(
-            Section::builder("iBoot".to_string(), base_addr..base_addr + parent_len)
+            Section::builder("iBoot".to_string(), base_addr..base_addr + decoded_len)
                 .semantics(Semantics::ReadOnlyCode)
                 .is_auto(true),
         );
@@ -170,16 +275,33 @@ impl AsRef<BinaryView> for iBootView {
 
 impl BinaryViewBase for iBootView {
     fn address_size(&self) -> usize {
-        8
+        self.address_size.get()
     }
 
     fn default_endianness(&self) -> Endianness {
-        Endianness::LittleEndian
+        self.endianness.get()
     }
 
     fn entry_point(&self) -> u64 {
         0
     }
+
+    fn len(&self) -> u64 {
+        self.decoded.borrow().len() as u64
+    }
+
+    /// Serves bytes from the decoded (unwrapped/decompressed) image, since the segment's
+    /// `parent_backing` now refers to this view's own data rather than the parent view's.
+    fn read(&self, buf: &mut [u8], offset: u64) -> usize {
+        let decoded = self.decoded.borrow();
+        let offset = offset as usize;
+        if offset >= decoded.len() {
+            return 0;
+        }
+        let n = buf.len().min(decoded.len() - offset);
+        buf[..n].copy_from_slice(&decoded[offset..offset + n]);
+        n
+    }
 }
 
 unsafe impl CustomBinaryView for iBootView {